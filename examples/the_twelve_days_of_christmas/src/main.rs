@@ -17,9 +17,9 @@ const DAYS: [&str; TOTAL_DAYS] = [
     "twelfth",
 ];
 
-const LYRICS: [&str; TOTAL_DAYS] = [
+const GIFTS: [&str; TOTAL_DAYS] = [
     "A partridge in a pear tree",
-    "Two turtle doves and",
+    "Two turtle doves",
     "Three french hens",
     "Four calling birds",
     "Five golden rings",
@@ -36,23 +36,273 @@ const LYRICS: [&str; TOTAL_DAYS] = [
 fn main() {
     println!("The Twelve Days of Christmas");
     println!("----------------------------");
-    for day in 0..TOTAL_DAYS {
-        println!();
-        print_first_verse_line(day);
-        print_lyrics(day);
+    println!();
+    println!("{}", build_carol());
+}
 
-    }
+/// Assembles the full carol, one verse per day, separated by a blank line.
+pub fn build_carol() -> String {
+    (0..TOTAL_DAYS)
+        .map(build_verse)
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+fn build_verse(day: usize) -> String {
+    let mut lines = vec![build_first_verse_line(day)];
+    lines.extend(build_lyrics(day));
+    lines.join("\n")
 }
 
-fn print_first_verse_line(day: usize) {
-    println!(
+fn build_first_verse_line(day: usize) -> String {
+    format!(
         "On the {} day of Christmas my true love sent to me",
         DAYS[day],
     )
 }
 
-fn print_lyrics(day: usize) {
-    for line in (0..=day).rev() {
-        println!("{}", LYRICS[line])
+fn build_lyrics(day: usize) -> Vec<String> {
+    let gifts: Vec<&str> = (0..=day).rev().map(|line| GIFTS[line]).collect();
+    render_gift_lines(&gifts)
+}
+
+/// Renders an ordered (largest gift first) list of gifts as verse lines: every
+/// gift but the last gets a trailing comma, and the last gift is prefixed with
+/// "and" unless it's the only gift in the verse, matching how the song is sung.
+fn render_gift_lines(gifts: &[&str]) -> Vec<String> {
+    let last = gifts.len() - 1;
+    gifts
+        .iter()
+        .enumerate()
+        .map(|(i, gift)| {
+            if i != last {
+                format!("{gift},")
+            } else if gifts.len() == 1 {
+                format!("{gift}.")
+            } else {
+                format!("and {}.", lowercase_first(gift))
+            }
+        })
+        .collect()
+}
+
+fn lowercase_first(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().collect::<String>() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+/// Returns an iterator over verses that grows the gift list one day at a time,
+/// inserting each new day's gift at the front, instead of recomputing the
+/// whole list from scratch like [`build_lyrics`] does. Produces the same
+/// verses as [`build_carol`]; callers can `.take(n)` to stop early.
+pub fn verses() -> impl Iterator<Item = String> {
+    VerseAccumulator {
+        day: 0,
+        gifts: Vec::new(),
     }
 }
+
+struct VerseAccumulator {
+    day: usize,
+    gifts: Vec<&'static str>,
+}
+
+impl Iterator for VerseAccumulator {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        if self.day >= TOTAL_DAYS {
+            return None;
+        }
+
+        self.gifts.insert(0, GIFTS[self.day]);
+        let mut lines = vec![build_first_verse_line(self.day)];
+        lines.extend(render_gift_lines(&self.gifts));
+        self.day += 1;
+        Some(lines.join("\n"))
+    }
+}
+
+/// Parses a rendered verse (the "On the Nth day..." header plus its gift
+/// lines, as produced by [`build_verse`] or [`verses`]) back into the day it
+/// represents, giving the carol a generate -> parse -> day round trip.
+pub fn parse_verse(verse: &str) -> Result<usize, ParseVerseError> {
+    let mut lines = verse.lines();
+
+    let header = lines.next().ok_or(ParseVerseError::MissingHeader)?;
+    let ordinal = header
+        .strip_prefix("On the ")
+        .and_then(|rest| rest.strip_suffix(" day of Christmas my true love sent to me"))
+        .ok_or(ParseVerseError::MissingHeader)?;
+    let day = DAYS
+        .iter()
+        .position(|&known| known == ordinal)
+        .ok_or_else(|| ParseVerseError::UnknownOrdinal(ordinal.to_string()))?;
+
+    let gift_lines: Vec<&str> = lines.collect();
+    let expected_gifts: Vec<&str> = (0..=day).rev().map(|line| GIFTS[line]).collect();
+    let expected_lines = render_gift_lines(&expected_gifts);
+
+    if gift_lines.len() != expected_lines.len() {
+        return Err(ParseVerseError::WrongGiftLineCount {
+            expected: expected_lines.len(),
+            found: gift_lines.len(),
+        });
+    }
+
+    for (i, (found, expected)) in gift_lines.iter().zip(expected_lines.iter()).enumerate() {
+        if found != expected {
+            return Err(ParseVerseError::UnexpectedGiftLine {
+                day: day - i,
+                expected: expected.clone(),
+                found: found.to_string(),
+            });
+        }
+    }
+
+    Ok(day)
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseVerseError {
+    /// The verse didn't start with a recognizable "On the Nth day..." header.
+    MissingHeader,
+    /// The header's ordinal (e.g. "thirteenth") isn't one of the known [`DAYS`].
+    UnknownOrdinal(String),
+    /// The verse had more or fewer gift lines than its day calls for.
+    WrongGiftLineCount { expected: usize, found: usize },
+    /// A gift line didn't match the known gift for that day, in that position.
+    UnexpectedGiftLine {
+        day: usize,
+        expected: String,
+        found: String,
+    },
+}
+
+impl std::fmt::Display for ParseVerseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseVerseError::MissingHeader => {
+                write!(f, "verse is missing its \"On the Nth day...\" header line")
+            }
+            ParseVerseError::UnknownOrdinal(ordinal) => {
+                write!(f, "unknown day ordinal: {ordinal:?}")
+            }
+            ParseVerseError::WrongGiftLineCount { expected, found } => {
+                write!(f, "expected {expected} gift line(s), found {found}")
+            }
+            ParseVerseError::UnexpectedGiftLine {
+                day,
+                expected,
+                found,
+            } => write!(
+                f,
+                "gift line for day {day} didn't match: expected {expected:?}, found {found:?}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseVerseError {}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_build_verse_first_day() {
+        assert_eq!(
+            build_verse(0),
+            "On the first day of Christmas my true love sent to me\n\
+             A partridge in a pear tree."
+        );
+    }
+
+    #[test]
+    fn test_build_verse_twelfth_day() {
+        assert_eq!(
+            build_verse(11),
+            "On the twelfth day of Christmas my true love sent to me\n\
+             Twelve drummers drumming,\n\
+             Eleven pipers piping,\n\
+             Ten lords a-leaping,\n\
+             Nine ladies dancing,\n\
+             Eight maids a-milking,\n\
+             Seven swans a-swimming,\n\
+             Six geese a-laying,\n\
+             Five golden rings,\n\
+             Four calling birds,\n\
+             Three french hens,\n\
+             Two turtle doves,\n\
+             and a partridge in a pear tree."
+        );
+    }
+
+    #[test]
+    fn test_verses_agrees_with_build_verse() {
+        let accumulated: Vec<String> = verses().collect();
+        let reverse_range: Vec<String> = (0..TOTAL_DAYS).map(build_verse).collect();
+        assert_eq!(accumulated, reverse_range);
+    }
+
+    #[test]
+    fn test_parse_verse_round_trips_every_day() {
+        for day in 0..TOTAL_DAYS {
+            assert_eq!(parse_verse(&build_verse(day)), Ok(day));
+        }
+    }
+
+    #[test]
+    fn test_parse_verse_missing_header() {
+        assert_eq!(
+            parse_verse("Twelve drummers drumming,\nA partridge in a pear tree."),
+            Err(ParseVerseError::MissingHeader)
+        );
+    }
+
+    #[test]
+    fn test_parse_verse_unknown_ordinal() {
+        assert_eq!(
+            parse_verse(
+                "On the thirteenth day of Christmas my true love sent to me\n\
+                 A partridge in a pear tree."
+            ),
+            Err(ParseVerseError::UnknownOrdinal("thirteenth".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_verse_wrong_gift_line_count() {
+        assert_eq!(
+            parse_verse(
+                "On the second day of Christmas my true love sent to me\n\
+                 and a partridge in a pear tree."
+            ),
+            Err(ParseVerseError::WrongGiftLineCount {
+                expected: 2,
+                found: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_verse_unexpected_gift_line() {
+        assert_eq!(
+            parse_verse(
+                "On the second day of Christmas my true love sent to me\n\
+                 Two turtle hens,\n\
+                 and a partridge in a pear tree."
+            ),
+            Err(ParseVerseError::UnexpectedGiftLine {
+                day: 1,
+                expected: "Two turtle doves,".to_string(),
+                found: "Two turtle hens,".to_string(),
+            })
+        );
+    }
+
+}